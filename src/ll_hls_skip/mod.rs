@@ -0,0 +1,455 @@
+//! Functionality for parsing and generating LL-HLS Delta playlists.
+mod playlist;
+
+pub(crate) use playlist::MediaPlaylist;
+use playlist::{BodyItem, Skip};
+
+/// SkipParams controls how a Delta playlist is generated.
+#[derive(Debug, PartialEq)]
+struct SkipParams {
+    /// Controls what gets skipped: segments or both segments and dateranges.
+    skip_param: String,
+    /// Segments or dateranges with offset_seconds older than this
+    /// should be skipped.
+    offset_cutoff_seconds: f64,
+    /// Whether or not dateranges can be skipped.
+    /// This value is not the same as `skip_param`, as it is provided by the server.
+    /// Both this and skip_param == v2 are needed to actually skip dateranges.
+    can_skip_dateranges: bool,
+}
+
+impl SkipParams {
+    /// Computes the params controlling how `playlist` should be skipped for
+    /// the given `_HLS_skip` value.
+    fn new(skip_val: &str, playlist: &MediaPlaylist) -> Self {
+        let can_skip_until = playlist
+            .server_control
+            .and_then(|sc| sc.can_skip_until)
+            .unwrap_or(0.0);
+        let can_skip_dateranges = playlist
+            .server_control
+            .map(|sc| sc.can_skip_dateranges)
+            .unwrap_or(false);
+
+        SkipParams {
+            skip_param: skip_val.into(),
+            offset_cutoff_seconds: playlist.total_duration() - can_skip_until,
+            can_skip_dateranges,
+        }
+    }
+
+    /// Defines a noop set of params that don't skip anything
+    /// when used with should_skip functions.
+    fn noop() -> Self {
+        SkipParams {
+            skip_param: "".to_string(),
+            offset_cutoff_seconds: 0f64,
+            can_skip_dateranges: false,
+        }
+    }
+
+    fn segment_should_skip(&self, offset_seconds: f64) -> bool {
+        (self.skip_param == "v2" || self.skip_param == "YES")
+            && self.offset_cutoff_seconds > offset_seconds
+    }
+
+    fn daterange_should_skip(&self, offset_seconds: f64) -> bool {
+        self.can_skip_dateranges
+            && self.skip_param == "v2"
+            && self.offset_cutoff_seconds > offset_seconds
+    }
+}
+
+impl MediaPlaylist {
+    /// The sum of every complete segment's and daterange's duration,
+    /// including the trailing run of parts that hasn't been closed out by
+    /// an `#EXTINF`/URI pair yet (if any).
+    ///
+    /// NOTE: There's technically an extra case here since #EXT-X-VERSION must be >= 9
+    /// for skipping to happen and >= 10 for dateranges to be skipped.
+    /// But, I've found playlists that respond to _HLS_skip with version < 9...
+    fn total_duration(&self) -> f64 {
+        let mut total = 0f64;
+        for item in &self.body {
+            match item {
+                BodyItem::Segment(seg) if seg.uri.is_some() => total += seg.duration,
+                BodyItem::Segment(seg) => total += seg.parts.iter().map(|p| p.duration).sum::<f64>(),
+                BodyItem::DateRange(dr) => total += dr.duration,
+            }
+        }
+        total
+    }
+}
+
+/// collapse_skipped parses the given playlist and applies a delta transformation if possible:
+/// - adds `#EXT-X-SKIP` tag to the playlist
+/// - removes the segments and optionally dateranges that were skipped.
+/// Returns a delta playlist if one was generated, otherwise it returns the original playlist.
+///
+/// The origin may itself already be serving a delta playlist (its own
+/// `#EXT-X-SKIP`). When that's the case, the counts computed here are added
+/// on top of what the origin already skipped rather than replacing them; if
+/// we don't end up skipping anything further, the origin's delta is passed
+/// through unchanged.
+pub(crate) fn collapse_skipped(skip_val: &str, playlist: String) -> String {
+    let mut parsed = MediaPlaylist::parse(&playlist);
+
+    if parsed.end_list {
+        // No skipping should happen in this case, return the playlist untouched.
+        return playlist;
+    }
+
+    let skip_params = SkipParams::new(skip_val, &parsed);
+
+    // Walk the body in order, tracking cumulative offset and which items
+    // are eligible to be skipped.
+    let mut running_offset = 0f64;
+    let mut should_skip = Vec::with_capacity(parsed.body.len());
+    for item in &parsed.body {
+        match item {
+            BodyItem::Segment(seg) if seg.uri.is_some() => {
+                running_offset += seg.duration;
+                should_skip.push(skip_params.segment_should_skip(running_offset));
+            }
+            // A trailing, not-yet-complete segment is never skippable.
+            BodyItem::Segment(_) => should_skip.push(false),
+            BodyItem::DateRange(dr) => {
+                running_offset += dr.duration;
+                should_skip.push(skip_params.daterange_should_skip(running_offset));
+            }
+        }
+    }
+
+    // Keep everything from the first non-skipped item onward.
+    let mut first_kept = should_skip.iter().position(|&skip| !skip).unwrap_or(parsed.body.len());
+
+    // A byte-range segment with no explicit start offset (`LENGTH` with no
+    // `@OFFSET`) inherits its offset from the segment immediately before
+    // it, so that predecessor must survive even if it was itself eligible
+    // to be skipped. Implicit offsets can chain across several consecutive
+    // segments, so keep backing up until we land on one with an explicit
+    // `@OFFSET` (or the start of the body).
+    while first_kept > 0 {
+        let needs_predecessor = matches!(
+            parsed.body.get(first_kept),
+            Some(BodyItem::Segment(seg)) if matches!(&seg.byterange, Some(br) if !br.contains('@'))
+        );
+        if !needs_predecessor {
+            break;
+        }
+        first_kept -= 1;
+    }
+
+    if first_kept == 0 {
+        // Nothing ends up getting skipped; return the original playlist unchanged.
+        return playlist;
+    }
+
+    // Rendition reports point at the latest (MSN, part) this playlist
+    // knows about; compute that from the full body (and factoring in any
+    // origin skip already applied), before any segments are dropped below.
+    let (last_msn, last_part) = parsed.available_msn_part();
+    for report in &mut parsed.rendition_reports {
+        report.last_msn = Some(last_msn);
+        report.last_part = last_part;
+    }
+
+    let origin_skip = parsed.skip.take();
+
+    let newly_skipped_segments = parsed.body[..first_kept]
+        .iter()
+        .filter(|item| matches!(item, BodyItem::Segment(_)))
+        .count() as u32;
+    let newly_skipped_daterange_ids: Vec<String> = parsed.body[..first_kept]
+        .iter()
+        .filter_map(|item| match item {
+            BodyItem::DateRange(dr) => Some(dr.id.clone()),
+            _ => None,
+        })
+        .collect();
+
+    let num_skipped_segments = origin_skip
+        .as_ref()
+        .map(|s| s.skipped_segments)
+        .unwrap_or(0)
+        + newly_skipped_segments;
+    let skipped_daterange_ids: Vec<String> = origin_skip
+        .map(|s| s.recently_removed_dateranges)
+        .unwrap_or_default()
+        .into_iter()
+        .chain(newly_skipped_daterange_ids)
+        .collect();
+
+    parsed.body.drain(..first_kept);
+
+    parsed.skip = Some(Skip {
+        skipped_segments: num_skipped_segments,
+        recently_removed_dateranges: skipped_daterange_ids,
+    });
+    if parsed.version.map(|v| v < 9).unwrap_or(true) {
+        parsed.version = Some(9);
+    }
+
+    parsed.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use playlist::MediaSegment;
+    use std::fs;
+    use std::path::Path;
+
+    fn non_delta_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/regular.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn delta_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/delta.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn fmp4_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/fmp4.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn fmp4_delta_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/fmp4_delta.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn origin_delta_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/origin_delta.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn origin_delta_recollapsed_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/origin_delta_recollapsed.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn fmp4_chain_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/fmp4_chain.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn fmp4_chain_delta_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/fmp4_chain_delta.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn discontinuity_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/discontinuity.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    fn discontinuity_delta_playlist() -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testdata/discontinuity_delta.m3u8");
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_playlist() {
+        let in_playlist = non_delta_playlist();
+        let parsed = MediaPlaylist::parse(&in_playlist);
+
+        let segment_count = parsed
+            .body
+            .iter()
+            .filter(|item| matches!(item, BodyItem::Segment(seg) if seg.uri.is_some()))
+            .count();
+        let daterange_count = parsed
+            .body
+            .iter()
+            .filter(|item| matches!(item, BodyItem::DateRange(_)))
+            .count();
+        assert_eq!(daterange_count, 0);
+        assert_eq!(segment_count, 7);
+
+        let params = SkipParams::new("YES", &parsed);
+        assert_eq!(
+            params,
+            SkipParams {
+                skip_param: String::from("YES"),
+                offset_cutoff_seconds: 19.33392,
+                can_skip_dateranges: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_delta_playlist() {
+        let in_playlist = non_delta_playlist();
+        let out_playlist = delta_playlist();
+        let generated_playlist = collapse_skipped("YES", in_playlist);
+
+        assert_eq!(
+            out_playlist, generated_playlist,
+            "\n\
+                   Expected playlist:\n\
+                        {}\n\
+                   Got playlist:\n\
+                        {}\n",
+            out_playlist, generated_playlist
+        );
+    }
+
+    #[test]
+    fn test_write_delta_playlist_preserves_map_and_byterange() {
+        let in_playlist = fmp4_playlist();
+        let out_playlist = fmp4_delta_playlist();
+        let generated_playlist = collapse_skipped("YES", in_playlist);
+
+        assert_eq!(
+            out_playlist, generated_playlist,
+            "\n\
+                   Expected playlist:\n\
+                        {}\n\
+                   Got playlist:\n\
+                        {}\n",
+            out_playlist, generated_playlist
+        );
+    }
+
+    #[test]
+    fn test_rewrites_rendition_report_last_msn_and_part() {
+        let generated_playlist = collapse_skipped("YES", non_delta_playlist());
+        let parsed = MediaPlaylist::parse(&generated_playlist);
+
+        assert_eq!(parsed.rendition_reports.len(), 1);
+        assert_eq!(parsed.rendition_reports[0].last_msn, Some(106));
+        assert_eq!(parsed.rendition_reports[0].last_part, Some(1));
+    }
+
+    #[test]
+    fn test_write_delta_playlist_walks_back_through_implicit_byterange_chain() {
+        // Segments 2-4 below share one implicit-offset chain anchored on
+        // segment 2's explicit `@OFFSET`; the skip boundary lands inside
+        // that chain (on segment 4), so only segment 1 can actually be
+        // dropped without leaving an unresolvable byte range up front.
+        let in_playlist = fmp4_chain_playlist();
+        let out_playlist = fmp4_chain_delta_playlist();
+        let generated_playlist = collapse_skipped("YES", in_playlist);
+
+        assert_eq!(
+            out_playlist, generated_playlist,
+            "\n\
+                   Expected playlist:\n\
+                        {}\n\
+                   Got playlist:\n\
+                        {}\n",
+            out_playlist, generated_playlist
+        );
+    }
+
+    #[test]
+    fn test_parse_attaches_mid_stream_tag_to_the_following_segment() {
+        let parsed = MediaPlaylist::parse(&discontinuity_playlist());
+
+        assert_eq!(parsed.header_extra, vec!["#EXT-M3U".to_string()]);
+
+        let segments: Vec<&MediaSegment> = parsed
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                BodyItem::Segment(seg) => Some(seg),
+                BodyItem::DateRange(_) => None,
+            })
+            .collect();
+        assert_eq!(segments[1].extra, vec!["#EXT-X-DISCONTINUITY".to_string()]);
+        assert_eq!(segments[2].extra, vec!["#EXT-X-KEY:METHOD=NONE".to_string()]);
+    }
+
+    #[test]
+    fn test_write_delta_playlist_drops_mid_stream_tag_with_its_segment() {
+        // #EXT-X-DISCONTINUITY belongs to (and is dropped along with) the
+        // segment right after it; #EXT-X-KEY belongs to the segment that
+        // survives and should still be there in the delta.
+        let in_playlist = discontinuity_playlist();
+        let out_playlist = discontinuity_delta_playlist();
+        let generated_playlist = collapse_skipped("YES", in_playlist);
+
+        assert_eq!(
+            out_playlist, generated_playlist,
+            "\n\
+                   Expected playlist:\n\
+                        {}\n\
+                   Got playlist:\n\
+                        {}\n",
+            out_playlist, generated_playlist
+        );
+    }
+
+    #[test]
+    fn test_available_msn_part_accounts_for_origin_skip() {
+        // media_sequence=102 with an origin-applied SKIPPED-SEGMENTS=2 means
+        // the real MSN of the first present (complete) segment is 104, not
+        // 102 -- so the last complete segment (the third one present) is
+        // 106, and the forming segment after it is 107.
+        let parsed = MediaPlaylist::parse(&origin_delta_playlist());
+
+        assert_eq!(parsed.available_msn_part(), (106, Some(0)));
+
+        assert!(parsed.satisfies(106, None));
+        assert!(!parsed.satisfies(107, None));
+        assert!(parsed.satisfies(107, Some(0)));
+        assert!(!parsed.satisfies(107, Some(1)));
+    }
+
+    #[test]
+    fn test_recollapse_merges_with_origin_skip() {
+        let in_playlist = origin_delta_playlist();
+        let out_playlist = origin_delta_recollapsed_playlist();
+        let generated_playlist = collapse_skipped("YES", in_playlist);
+
+        assert_eq!(
+            out_playlist, generated_playlist,
+            "\n\
+                   Expected playlist:\n\
+                        {}\n\
+                   Got playlist:\n\
+                        {}\n",
+            out_playlist, generated_playlist
+        );
+    }
+
+    #[test]
+    fn test_recollapse_passes_through_when_origin_skip_already_sufficient() {
+        let in_playlist = origin_delta_playlist();
+        // The first pass skips one more segment on top of the origin's own
+        // skip. Fed back in as an origin delta, that result already skips
+        // everything our cutoff would have skipped; nothing further is
+        // eligible, so it should come back byte-for-byte unchanged.
+        let generated_playlist = collapse_skipped("YES", in_playlist.clone());
+        let reparsed_once_more = collapse_skipped("YES", generated_playlist.clone());
+
+        assert_eq!(reparsed_once_more, generated_playlist);
+        assert_ne!(generated_playlist, in_playlist);
+    }
+
+    #[test]
+    fn test_satisfies_blocking_reload_for_part_of_forming_segment() {
+        // fileSequence106 is complete; 107 is forming with parts 0 and 1
+        // available. A `_HLS_part` request for 107 should be judged against
+        // 107's own part count, not whichever segment happens to be last.
+        let parsed = MediaPlaylist::parse(&non_delta_playlist());
+
+        assert!(parsed.satisfies(107, Some(0)));
+        assert!(parsed.satisfies(107, Some(1)));
+        assert!(!parsed.satisfies(107, Some(2)));
+        assert!(!parsed.satisfies(108, Some(0)));
+
+        assert!(parsed.satisfies(106, None));
+        assert!(!parsed.satisfies(107, None));
+    }
+
+    #[test]
+    fn test_noop_skip_params_never_skip() {
+        let params = SkipParams::noop();
+        assert!(!params.segment_should_skip(0.0));
+        assert!(!params.daterange_should_skip(0.0));
+    }
+}
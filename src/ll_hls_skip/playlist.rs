@@ -0,0 +1,609 @@
+//! A structured, panic-free representation of an LL-HLS media playlist.
+//!
+//! This is intentionally not a full implementation of the HLS playlist
+//! grammar (see the `m3u8-rs` / `hls_m3u8` crates for that) — it models just
+//! enough of the low-latency tag set (`#EXT-X-SERVER-CONTROL`,
+//! `#EXT-X-PART-INF`, `#EXT-X-SKIP`, `#EXT-X-PART`, `#EXT-X-PRELOAD-HINT`,
+//! `#EXT-X-RENDITION-REPORT`) for `ll_hls_skip` to reason about and
+//! re-serialize a playlist without re-walking raw lines by hand.
+use std::fmt;
+
+/// A parsed media playlist.
+///
+/// Tags this module doesn't have a dedicated field for are preserved
+/// verbatim in `header_extra` (for header-level tags) or a segment's
+/// `extra` (for per-segment tags) so round-tripping through `Display`
+/// doesn't silently drop them.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct MediaPlaylist {
+    pub(crate) version: Option<u32>,
+    pub(crate) target_duration: Option<u32>,
+    pub(crate) media_sequence: u64,
+    pub(crate) discontinuity_sequence: Option<u64>,
+    pub(crate) part_inf: Option<PartInf>,
+    pub(crate) server_control: Option<ServerControl>,
+    pub(crate) skip: Option<Skip>,
+    /// Header lines with no dedicated field (e.g. `#EXT-M3U`,
+    /// `#EXT-X-PLAYLIST-TYPE`), kept verbatim and in order.
+    pub(crate) header_extra: Vec<String>,
+    /// Segments and dateranges, in the order they appeared in the playlist.
+    pub(crate) body: Vec<BodyItem>,
+    pub(crate) preload_hints: Vec<PreloadHint>,
+    pub(crate) rendition_reports: Vec<RenditionReport>,
+    pub(crate) end_list: bool,
+}
+
+/// An entry in the playlist body: either a (possibly partial) media segment
+/// or a `#EXT-X-DATERANGE`. Kept as a single ordered sequence because
+/// skip eligibility is computed over both in playlist order.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum BodyItem {
+    Segment(MediaSegment),
+    DateRange(DateRange),
+}
+
+/// A media segment, or a trailing run of `#EXT-X-PART`s not yet closed out
+/// by an `#EXTINF`/URI pair (`uri` is `None` in that case).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct MediaSegment {
+    pub(crate) duration: f64,
+    /// Text following the comma on the `#EXTINF` line (usually empty).
+    pub(crate) title: String,
+    /// `#EXT-X-BYTERANGE` value, e.g. `"76242@0"` or `"76242"` (an implicit
+    /// offset, continuing on from the previous segment's range).
+    pub(crate) byterange: Option<String>,
+    pub(crate) program_date_time: Option<String>,
+    pub(crate) parts: Vec<Part>,
+    pub(crate) uri: Option<String>,
+    /// The `#EXT-X-MAP` line that applies to this segment, resolved from
+    /// whichever `#EXT-X-MAP` tag most recently preceded it (an `#EXT-X-MAP`
+    /// tag applies to every segment after it until the next one). `None` if
+    /// no `#EXT-X-MAP` has appeared yet.
+    pub(crate) map: Option<String>,
+    /// Other per-segment tags (e.g. `#EXT-X-KEY`), preserved verbatim.
+    pub(crate) extra: Vec<String>,
+}
+
+/// A `#EXT-X-PART` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Part {
+    pub(crate) duration: f64,
+    pub(crate) uri: String,
+    pub(crate) independent: bool,
+}
+
+/// A `#EXT-X-DATERANGE` entry. Only the attributes `ll_hls_skip` needs to
+/// reason about skip eligibility are pulled out; the rest of the tag is
+/// kept in `raw` so it round-trips unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DateRange {
+    pub(crate) id: String,
+    pub(crate) duration: f64,
+    pub(crate) raw: String,
+}
+
+/// `#EXT-X-SERVER-CONTROL` attributes.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ServerControl {
+    pub(crate) can_skip_until: Option<f64>,
+    pub(crate) can_skip_dateranges: bool,
+    pub(crate) part_hold_back: Option<f64>,
+    pub(crate) can_block_reload: bool,
+}
+
+/// `#EXT-X-PART-INF` attributes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PartInf {
+    pub(crate) part_target: f64,
+}
+
+/// `#EXT-X-SKIP` attributes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Skip {
+    pub(crate) skipped_segments: u32,
+    pub(crate) recently_removed_dateranges: Vec<String>,
+}
+
+/// `#EXT-X-PRELOAD-HINT` attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PreloadHint {
+    pub(crate) hint_type: String,
+    pub(crate) uri: String,
+    pub(crate) byterange_start: Option<u64>,
+    pub(crate) byterange_length: Option<u64>,
+}
+
+/// `#EXT-X-RENDITION-REPORT` attributes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RenditionReport {
+    pub(crate) uri: String,
+    pub(crate) last_msn: Option<u64>,
+    pub(crate) last_part: Option<u64>,
+}
+
+/// Splits an attribute-list value (the part after the tag's `:`) on commas
+/// that aren't inside a quoted string, e.g. `A=1,B="x,y",C=2` -> `["A=1",
+/// "B=\"x,y\"", "C=2"]`.
+fn split_attrs(value: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(&value[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+/// Splits a single `KEY=VALUE` attribute, returning `None` for malformed
+/// attributes rather than panicking.
+fn attr_kv(attr: &str) -> Option<(&str, &str)> {
+    attr.split_once('=')
+}
+
+/// Strips one layer of surrounding double quotes, if present.
+fn unquote(value: &str) -> &str {
+    value.strip_prefix('"').unwrap_or(value).strip_suffix('"').unwrap_or(value)
+}
+
+fn parse_server_control(value: &str) -> ServerControl {
+    let mut sc = ServerControl::default();
+    for attr in split_attrs(value) {
+        let Some((key, val)) = attr_kv(attr) else {
+            continue;
+        };
+        match key {
+            "CAN-SKIP-UNTIL" => sc.can_skip_until = val.parse().ok(),
+            "CAN-SKIP-DATERANGES" => sc.can_skip_dateranges = val == "YES",
+            "PART-HOLD-BACK" => sc.part_hold_back = val.parse().ok(),
+            "CAN-BLOCK-RELOAD" => sc.can_block_reload = val == "YES",
+            _ => {}
+        }
+    }
+    sc
+}
+
+fn parse_skip(value: &str) -> Skip {
+    let mut skip = Skip::default();
+    for attr in split_attrs(value) {
+        let Some((key, val)) = attr_kv(attr) else {
+            continue;
+        };
+        match key {
+            "SKIPPED-SEGMENTS" => skip.skipped_segments = val.parse().unwrap_or(0),
+            "RECENTLY-REMOVED-DATERANGES" => {
+                skip.recently_removed_dateranges =
+                    val.split('\t').map(|s| s.to_string()).collect();
+            }
+            _ => {}
+        }
+    }
+    skip
+}
+
+fn parse_part(value: &str) -> Option<Part> {
+    let mut duration = None;
+    let mut uri = None;
+    let mut independent = false;
+    for attr in split_attrs(value) {
+        let Some((key, val)) = attr_kv(attr) else {
+            continue;
+        };
+        match key {
+            "DURATION" => duration = val.parse().ok(),
+            "URI" => uri = Some(unquote(val).to_string()),
+            "INDEPENDENT" => independent = val == "YES",
+            _ => {}
+        }
+    }
+    Some(Part {
+        duration: duration?,
+        uri: uri?,
+        independent,
+    })
+}
+
+fn parse_daterange(value: &str, raw_line: &str) -> DateRange {
+    let mut id = String::new();
+    let mut duration = 0f64;
+    for attr in split_attrs(value) {
+        let Some((key, val)) = attr_kv(attr) else {
+            continue;
+        };
+        match key {
+            "ID" => id = unquote(val).to_string(),
+            "DURATION" => duration = val.parse().unwrap_or(0.0),
+            _ => {}
+        }
+    }
+    DateRange {
+        id,
+        duration,
+        raw: raw_line.to_string(),
+    }
+}
+
+fn parse_preload_hint(value: &str) -> Option<PreloadHint> {
+    let mut hint_type = None;
+    let mut uri = None;
+    let mut byterange_start = None;
+    let mut byterange_length = None;
+    for attr in split_attrs(value) {
+        let Some((key, val)) = attr_kv(attr) else {
+            continue;
+        };
+        match key {
+            "TYPE" => hint_type = Some(val.to_string()),
+            "URI" => uri = Some(unquote(val).to_string()),
+            "BYTERANGE-START" => byterange_start = val.parse().ok(),
+            "BYTERANGE-LENGTH" => byterange_length = val.parse().ok(),
+            _ => {}
+        }
+    }
+    Some(PreloadHint {
+        hint_type: hint_type?,
+        uri: uri?,
+        byterange_start,
+        byterange_length,
+    })
+}
+
+fn parse_rendition_report(value: &str) -> Option<RenditionReport> {
+    let mut uri = None;
+    let mut last_msn = None;
+    let mut last_part = None;
+    for attr in split_attrs(value) {
+        let Some((key, val)) = attr_kv(attr) else {
+            continue;
+        };
+        match key {
+            "URI" => uri = Some(unquote(val).to_string()),
+            "LAST-MSN" => last_msn = val.parse().ok(),
+            "LAST-PART" => last_part = val.parse().ok(),
+            _ => {}
+        }
+    }
+    Some(RenditionReport {
+        uri: uri?,
+        last_msn,
+        last_part,
+    })
+}
+
+impl MediaPlaylist {
+    /// Parses a playlist into its structured form. Malformed tags are
+    /// skipped rather than causing a panic; unrecognized tags are kept
+    /// verbatim so they survive re-serialization.
+    pub(crate) fn parse(playlist: &str) -> MediaPlaylist {
+        let mut pl = MediaPlaylist::default();
+        let mut pending: Option<MediaSegment> = None;
+        // The most recently seen #EXT-X-MAP, which applies to every
+        // segment after it until a new one appears.
+        let mut current_map: Option<String> = None;
+
+        for line in playlist.lines() {
+            if let Some(value) = line.strip_prefix("#EXT-X-VERSION:") {
+                pl.version = value.trim().parse().ok();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+                pl.target_duration = value.trim().parse().ok();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-MEDIA-SEQUENCE:") {
+                pl.media_sequence = value.trim().parse().unwrap_or(0);
+            } else if let Some(value) = line.strip_prefix("#EXT-X-DISCONTINUITY-SEQUENCE:") {
+                pl.discontinuity_sequence = value.trim().parse().ok();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-PART-INF:") {
+                for attr in split_attrs(value) {
+                    if let Some(("PART-TARGET", val)) = attr_kv(attr) {
+                        if let Ok(part_target) = val.parse() {
+                            pl.part_inf = Some(PartInf { part_target });
+                        }
+                    }
+                }
+            } else if let Some(value) = line.strip_prefix("#EXT-X-SERVER-CONTROL:") {
+                pl.server_control = Some(parse_server_control(value));
+            } else if let Some(value) = line.strip_prefix("#EXT-X-SKIP:") {
+                pl.skip = Some(parse_skip(value));
+            } else if let Some(value) = line.strip_prefix("#EXT-X-PRELOAD-HINT:") {
+                if let Some(hint) = parse_preload_hint(value) {
+                    pl.preload_hints.push(hint);
+                }
+            } else if let Some(value) = line.strip_prefix("#EXT-X-RENDITION-REPORT:") {
+                if let Some(report) = parse_rendition_report(value) {
+                    pl.rendition_reports.push(report);
+                }
+            } else if let Some(value) = line.strip_prefix("#EXTINF:") {
+                // #EXT-X-PART entries for a segment precede its #EXTINF, so
+                // the pending segment (if any) is the one this completes.
+                let seg = pending.get_or_insert_with(MediaSegment::default);
+                let mut fields = value.splitn(2, ',');
+                seg.duration = fields.next().unwrap_or("").trim().parse().unwrap_or(0.0);
+                seg.title = fields.next().unwrap_or("").to_string();
+            } else if let Some(value) = line.strip_prefix("#EXT-X-BYTERANGE:") {
+                pending.get_or_insert_with(MediaSegment::default).byterange =
+                    Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("#EXT-X-PROGRAM-DATE-TIME:") {
+                pending.get_or_insert_with(MediaSegment::default).program_date_time =
+                    Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("#EXT-X-PART:") {
+                if let Some(part) = parse_part(value) {
+                    pending.get_or_insert_with(MediaSegment::default).parts.push(part);
+                }
+            } else if let Some(value) = line.strip_prefix("#EXT-X-DATERANGE:") {
+                pl.body.push(BodyItem::DateRange(parse_daterange(value, line)));
+            } else if line.starts_with("#EXT-X-MAP:") {
+                current_map = Some(line.to_string());
+            } else if line.starts_with("#EXT-X-ENDLIST") {
+                pl.end_list = true;
+            } else if line.starts_with('#') {
+                match &mut pending {
+                    Some(seg) => seg.extra.push(line.to_string()),
+                    // A tag with nowhere else to go belongs to the body
+                    // (e.g. #EXT-X-DISCONTINUITY between two segments) once
+                    // the first segment or daterange has been seen; only
+                    // before that point is it a true header-level tag.
+                    None if pl.body.is_empty() => pl.header_extra.push(line.to_string()),
+                    None => {
+                        pending = Some(MediaSegment {
+                            extra: vec![line.to_string()],
+                            ..MediaSegment::default()
+                        });
+                    }
+                }
+            } else if !line.trim().is_empty() {
+                // A bare URI line closes out the segment it belongs to.
+                let mut seg = pending.take().unwrap_or_default();
+                seg.uri = Some(line.to_string());
+                seg.map = current_map.clone();
+                pl.body.push(BodyItem::Segment(seg));
+            }
+        }
+
+        if let Some(mut seg) = pending {
+            // A trailing run of #EXT-X-PARTs with no closing URI yet.
+            seg.map = current_map;
+            pl.body.push(BodyItem::Segment(seg));
+        }
+
+        pl
+    }
+
+    /// The number of segments this playlist's own `#EXT-X-SKIP` (if any)
+    /// says came before the first segment actually present in the body.
+    /// `#EXT-X-MEDIA-SEQUENCE` is unaffected by skipping, so this offset
+    /// has to be added back in on top of it to land on the real MSN of
+    /// whatever's actually in `body`.
+    fn already_skipped_segments(&self) -> u64 {
+        self.skip.as_ref().map(|s| s.skipped_segments as u64).unwrap_or(0)
+    }
+
+    /// The most recently available (media sequence, part index) this
+    /// playlist can serve. `part` is `None` when the last segment is
+    /// already complete (i.e. any part of it is available).
+    pub(crate) fn available_msn_part(&self) -> (u64, Option<u64>) {
+        let complete_segments = self
+            .body
+            .iter()
+            .filter(|item| matches!(item, BodyItem::Segment(seg) if seg.uri.is_some()))
+            .count() as u64;
+        let last_msn = self.media_sequence + self.already_skipped_segments()
+            + complete_segments.saturating_sub(1);
+
+        let last_part = match self.body.last() {
+            Some(BodyItem::Segment(seg)) if seg.uri.is_none() => {
+                seg.parts.len().checked_sub(1).map(|idx| idx as u64)
+            }
+            _ => None,
+        };
+
+        (last_msn, last_part)
+    }
+
+    /// The media sequence number of the segment currently being assembled
+    /// (the trailing run of `#EXT-X-PART`s, if any) and the index of the
+    /// latest part available for it. Unlike `available_msn_part`, both
+    /// numbers here describe the *same* segment, which is what a
+    /// `_HLS_part` blocking-reload check needs: if the body ends on a
+    /// complete segment rather than a forming one, the MSN of the segment
+    /// that comes after it is returned with `None`, since none of that
+    /// successor's parts exist yet.
+    fn forming_segment(&self) -> (u64, Option<u64>) {
+        let complete_segments = self
+            .body
+            .iter()
+            .filter(|item| matches!(item, BodyItem::Segment(seg) if seg.uri.is_some()))
+            .count() as u64;
+        let base_msn = self.media_sequence + self.already_skipped_segments() + complete_segments;
+
+        match self.body.last() {
+            Some(BodyItem::Segment(seg)) if seg.uri.is_none() => {
+                let last_part = seg.parts.len().checked_sub(1).map(|idx| idx as u64);
+                (base_msn, last_part)
+            }
+            _ => (base_msn, None),
+        }
+    }
+
+    /// Whether this playlist has advanced far enough to satisfy a
+    /// `_HLS_msn`/`_HLS_part` blocking-reload request: either a later media
+    /// sequence is already available, or the requested sequence has reached
+    /// at least the requested part.
+    pub(crate) fn satisfies(&self, requested_msn: u64, requested_part: Option<u64>) -> bool {
+        match requested_part {
+            // A bare MSN request wants that segment fully available, not
+            // merely started.
+            None => self.available_msn_part().0 >= requested_msn,
+            Some(part) => {
+                let (forming_msn, forming_part) = self.forming_segment();
+                forming_msn > requested_msn
+                    || (forming_msn == requested_msn
+                        && forming_part.map_or(false, |available| available >= part))
+            }
+        }
+    }
+}
+
+impl fmt::Display for ServerControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut attrs = Vec::new();
+        if let Some(until) = self.can_skip_until {
+            attrs.push(format!("CAN-SKIP-UNTIL={:.5}", until));
+        }
+        attrs.push(format!(
+            "CAN-SKIP-DATERANGES={}",
+            if self.can_skip_dateranges { "YES" } else { "NO" }
+        ));
+        if let Some(hold_back) = self.part_hold_back {
+            attrs.push(format!("PART-HOLD-BACK={:.5}", hold_back));
+        }
+        attrs.push(format!(
+            "CAN-BLOCK-RELOAD={}",
+            if self.can_block_reload { "YES" } else { "NO" }
+        ));
+        write!(f, "#EXT-X-SERVER-CONTROL:{}", attrs.join(","))
+    }
+}
+
+impl fmt::Display for PartInf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#EXT-X-PART-INF:PART-TARGET={:.5}", self.part_target)
+    }
+}
+
+impl fmt::Display for Skip {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut attrs = vec![format!("SKIPPED-SEGMENTS={}", self.skipped_segments)];
+        if !self.recently_removed_dateranges.is_empty() {
+            attrs.push(format!(
+                "RECENTLY-REMOVED-DATERANGES={}",
+                self.recently_removed_dateranges.join("\t")
+            ));
+        }
+        write!(f, "#EXT-X-SKIP:{}", attrs.join(","))
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#EXT-X-PART:DURATION={:.5},URI=\"{}\"", self.duration, self.uri)?;
+        if self.independent {
+            write!(f, ",INDEPENDENT=YES")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MediaSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for extra in &self.extra {
+            writeln!(f, "{}", extra)?;
+        }
+        if let Some(pdt) = &self.program_date_time {
+            writeln!(f, "#EXT-X-PROGRAM-DATE-TIME:{}", pdt)?;
+        }
+        for part in &self.parts {
+            writeln!(f, "{}", part)?;
+        }
+        if self.uri.is_some() {
+            writeln!(f, "#EXTINF:{:.5},{}", self.duration, self.title)?;
+            if let Some(byterange) = &self.byterange {
+                writeln!(f, "#EXT-X-BYTERANGE:{}", byterange)?;
+            }
+            write!(f, "{}", self.uri.as_ref().unwrap())?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PreloadHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#EXT-X-PRELOAD-HINT:TYPE={},URI=\"{}\"", self.hint_type, self.uri)?;
+        if let Some(start) = self.byterange_start {
+            write!(f, ",BYTERANGE-START={}", start)?;
+        }
+        if let Some(length) = self.byterange_length {
+            write!(f, ",BYTERANGE-LENGTH={}", length)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for RenditionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#EXT-X-RENDITION-REPORT:URI=\"{}\"", self.uri)?;
+        if let Some(msn) = self.last_msn {
+            write!(f, ",LAST-MSN={}", msn)?;
+        }
+        if let Some(part) = self.last_part {
+            write!(f, ",LAST-PART={}", part)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for BodyItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodyItem::Segment(seg) => write!(f, "{}", seg),
+            BodyItem::DateRange(dr) => writeln!(f, "{}", dr.raw),
+        }
+    }
+}
+
+impl fmt::Display for MediaPlaylist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.header_extra {
+            writeln!(f, "{}", line)?;
+        }
+        if let Some(version) = self.version {
+            writeln!(f, "#EXT-X-VERSION:{}", version)?;
+        }
+        if let Some(target_duration) = self.target_duration {
+            writeln!(f, "#EXT-X-TARGETDURATION:{}", target_duration)?;
+        }
+        writeln!(f, "#EXT-X-MEDIA-SEQUENCE:{}", self.media_sequence)?;
+        if let Some(discontinuity_sequence) = self.discontinuity_sequence {
+            writeln!(f, "#EXT-X-DISCONTINUITY-SEQUENCE:{}", discontinuity_sequence)?;
+        }
+        if let Some(part_inf) = &self.part_inf {
+            writeln!(f, "{}", part_inf)?;
+        }
+        if let Some(server_control) = &self.server_control {
+            writeln!(f, "{}", server_control)?;
+        }
+        if let Some(skip) = &self.skip {
+            writeln!(f, "{}", skip)?;
+        }
+        // Only emit #EXT-X-MAP when it changes from the previous segment
+        // in the (possibly skip-truncated) body, rather than on every
+        // segment it applies to.
+        let mut prev_map: Option<&str> = None;
+        for item in &self.body {
+            if let BodyItem::Segment(seg) = item {
+                if seg.map.as_deref() != prev_map {
+                    if let Some(map_line) = &seg.map {
+                        writeln!(f, "{}", map_line)?;
+                    }
+                    prev_map = seg.map.as_deref();
+                }
+            }
+            write!(f, "{}", item)?;
+        }
+        for hint in &self.preload_hints {
+            writeln!(f, "{}", hint)?;
+        }
+        for report in &self.rendition_reports {
+            writeln!(f, "{}", report)?;
+        }
+        if self.end_list {
+            writeln!(f, "#EXT-X-ENDLIST")?;
+        }
+        Ok(())
+    }
+}
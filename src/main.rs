@@ -1,10 +1,12 @@
 //! POC for serving LL-HLS on Compute@Edge.
 mod ll_hls_skip;
-use crate::ll_hls_skip::collapse_skipped;
+use crate::ll_hls_skip::{collapse_skipped, MediaPlaylist};
 
 use fastly::http::{header, Method, StatusCode};
 use fastly::{Error, Request, Response};
 use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
 
 /// Names of backend servers associated with this service.
 /// These are the names of the origin servers provided for the Fastly service
@@ -17,6 +19,18 @@ const ALT_PATH_PREFIX: &str = "/alt";
 const BACKEND_PLAYLIST_PATH: &str = "/LowLatencyBBB/myStream/playlist.m3u8";
 const BACKEND_ALT_PLAYLIST_PATH: &str = "/LowLatencyBBB-EU/myStream/playlist.m3u8";
 
+/// Query params a blocking playlist reload request carries.
+const HLS_MSN_PARAM: &str = "_HLS_msn";
+const HLS_PART_PARAM: &str = "_HLS_part";
+
+/// How long to wait between re-polling the backend for a blocking reload.
+const BLOCKING_RELOAD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// Multiple of the playlist's part target used as the blocking-reload
+/// timeout, per the LL-HLS recommendation to bound how long a client waits.
+const BLOCKING_RELOAD_TIMEOUT_PART_TARGET_MULTIPLE: f64 = 3.0;
+/// Fallback timeout when the playlist has no `#EXT-X-PART-INF`.
+const BLOCKING_RELOAD_DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
 /// home returns HTML for a simple homepage.
 fn home() -> Result<(), Error> {
     let html = format!(
@@ -42,7 +56,9 @@ fn home() -> Result<(), Error> {
 }
 
 /// handle_req deals with making the request to the appropriate backend.
-/// For delta playlist requests, performs
+/// Handles `_HLS_skip` delta playlists and `_HLS_msn`/`_HLS_part` blocking
+/// reloads before passing the response (or an in-place transform of it)
+/// back to the client.
 fn handle_req(mut req: Request) -> Result<(), Error> {
     let mut backend = BACKEND_NAME;
 
@@ -64,6 +80,8 @@ fn handle_req(mut req: Request) -> Result<(), Error> {
         Some(sv) => sv,
         None => "",
     };
+    let requested_msn: Option<u64> = qp.get(HLS_MSN_PARAM).and_then(|v| v.parse().ok());
+    let requested_part: Option<u64> = qp.get(HLS_PART_PARAM).and_then(|v| v.parse().ok());
 
     // Don't cache playlists, although they should ideally be cached w/ 500ms TTL.
     // TODO(@phu): Revisit when we can specify 500ms TTL in C@E.
@@ -71,6 +89,29 @@ fn handle_req(mut req: Request) -> Result<(), Error> {
         new.set_pass(true);
     }
 
+    if let Some(requested_msn) = requested_msn {
+        // Blocking playlist reload: strip the LL-HLS params the backend
+        // doesn't need to calculate a delta playlist, then hold the
+        // response until the playlist has advanced far enough (or we
+        // time out).
+        let mut query_params = qp.clone();
+        query_params.remove(HLS_MSN_PARAM);
+        query_params.remove(HLS_PART_PARAM);
+        query_params.remove("_HLS_skip");
+        new.set_query(&query_params)?;
+
+        let mut final_resp = fetch_blocking_reload(&mut new, backend, requested_msn, requested_part)?;
+        if skip_val == "YES" || skip_val == "v2" {
+            let mut new_resp = final_resp.clone_with_body();
+            let delta_playlist = collapse_skipped(skip_val, new_resp.take_body().into_string());
+            new_resp.set_body(delta_playlist);
+            new_resp.send_to_client();
+        } else {
+            final_resp.send_to_client();
+        }
+        return Ok(());
+    }
+
     if skip_val == "YES" || skip_val == "v2" {
         let mut query_params = qp.clone();
         // Request the playlist without a skip param,
@@ -90,6 +131,53 @@ fn handle_req(mut req: Request) -> Result<(), Error> {
     Ok(())
 }
 
+/// Re-requests `req` against `backend` until the playlist it returns has
+/// advanced to at least `requested_msn`/`requested_part`, or a bounded
+/// timeout elapses. Gated by the playlist's own
+/// `ServerControl.can_block_reload` flag: if the origin doesn't advertise
+/// support for blocking reload, the first response is returned as-is.
+/// Returns the full backend response (headers included), not just its
+/// body, so callers can serve it on without losing things like
+/// `Content-Type`/`Cache-Control`.
+fn fetch_blocking_reload(
+    req: &mut Request,
+    backend: &str,
+    requested_msn: u64,
+    requested_part: Option<u64>,
+) -> Result<Response, Error> {
+    let mut be_resp = req.clone_with_body().send(backend)?;
+    let mut playlist = MediaPlaylist::parse(&be_resp.clone_with_body().take_body().into_string());
+
+    let can_block_reload = playlist
+        .server_control
+        .map(|sc| sc.can_block_reload)
+        .unwrap_or(false);
+    if !can_block_reload {
+        return Ok(be_resp);
+    }
+
+    // `Duration::from_secs_f64` panics on a negative, NaN, or infinite
+    // input, and `PART-TARGET` is attacker/origin-controlled, so only trust
+    // it when it parses to something actually usable as a timeout.
+    let timeout = playlist
+        .part_inf
+        .map(|part_inf| part_inf.part_target * BLOCKING_RELOAD_TIMEOUT_PART_TARGET_MULTIPLE)
+        .filter(|secs| secs.is_finite() && *secs > 0.0)
+        .map(Duration::from_secs_f64)
+        .unwrap_or(BLOCKING_RELOAD_DEFAULT_TIMEOUT);
+
+    let mut elapsed = Duration::from_secs(0);
+    while !playlist.satisfies(requested_msn, requested_part) && elapsed < timeout {
+        thread::sleep(BLOCKING_RELOAD_POLL_INTERVAL);
+        elapsed += BLOCKING_RELOAD_POLL_INTERVAL;
+
+        be_resp = req.clone_with_body().send(backend)?;
+        playlist = MediaPlaylist::parse(&be_resp.clone_with_body().take_body().into_string());
+    }
+
+    Ok(be_resp)
+}
+
 fn main() -> Result<(), Error> {
     let req = Request::from_client();
 